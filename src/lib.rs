@@ -0,0 +1,15 @@
+/*!
+This crate implements generators, a.k.a. coroutines, using only safe Rust code (well, almost).
+See the [`stack`] module for the generator backed by (non-allocating) stack storage.
+*/
+
+pub mod stack;
+
+/// The result of resuming a generator.
+#[derive(Debug, Eq, PartialEq)]
+pub enum GeneratorState<Y, C> {
+    /// The generator yielded a value, and is not done.
+    Yielded(Y),
+    /// The generator returned a completion value, and is done.
+    Complete(C),
+}
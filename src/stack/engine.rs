@@ -0,0 +1,126 @@
+//! Low-level plumbing shared between a generator's producer and whatever is
+//! driving it.
+
+use std::{
+    cell::UnsafeCell,
+    future::Future,
+    marker::PhantomData,
+    mem,
+    pin::Pin,
+    ptr::NonNull,
+    task::{Context, Poll},
+};
+
+/// The one-slot mailbox a generator's producer uses to hand a value out to
+/// its driver, and the driver uses to hand a resume argument back in.
+pub(crate) enum Airlock<Y, R> {
+    Empty,
+    Yielded(Y),
+    Resumed(R),
+}
+
+impl<Y, R> Airlock<Y, R> {
+    fn take_yielded(&mut self) -> Option<Y> {
+        match mem::replace(self, Airlock::Empty) {
+            Airlock::Yielded(value) => Some(value),
+            other => {
+                *self = other;
+                None
+            }
+        }
+    }
+}
+
+/// The interface a generator's producer uses to communicate with whatever is
+/// driving it.
+///
+/// You can get an instance of this type by defining an `async fn` and giving
+/// it a parameter of type `Co<'_, Y, R>`, where `Y` is the type of value the
+/// generator yields and `R` is the type of value that gets passed back in
+/// through `resume_with`.
+pub struct Co<'a, Y, R = ()> {
+    pub(crate) airlock: NonNull<UnsafeCell<Airlock<Y, R>>>,
+    pub(crate) phantom: PhantomData<&'a ()>,
+}
+
+impl<'a, Y, R> Co<'a, Y, R> {
+    /// Yields a value from the generator.
+    ///
+    /// Callers should immediately `.await` the result of this function, and
+    /// propagate it with `?` if resuming may abort the producer.
+    pub async fn yield_(&mut self, value: Y) -> R {
+        // Safety: this pointer stays valid for at least `'a`, because
+        // `Gen::new` requires the `Shelf` it points into to be borrowed for
+        // that whole lifetime.
+        unsafe {
+            *self.airlock.as_ref().get() = Airlock::Yielded(value);
+        }
+        YieldFut { airlock: self.airlock, phantom: PhantomData }.await
+    }
+}
+
+/// The future `Co::yield_` awaits; it resolves once the driver has placed a
+/// resume argument into the airlock.
+struct YieldFut<'a, Y, R> {
+    airlock: NonNull<UnsafeCell<Airlock<Y, R>>>,
+    phantom: PhantomData<&'a ()>,
+}
+
+impl<Y, R> Future for YieldFut<'_, Y, R> {
+    type Output = R;
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: see `Co::yield_`.
+        let slot = unsafe { &mut *self.airlock.as_ref().get() };
+        match mem::replace(slot, Airlock::Empty) {
+            Airlock::Resumed(resume_arg) => Poll::Ready(resume_arg),
+            other => {
+                *slot = other;
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// What happened when [`drive`] polled the producer.
+pub(crate) enum EngineOutput<Y, C> {
+    Yielded(Y),
+    Complete(C),
+}
+
+/// Polls `future` once, distinguishing the two reasons it can return
+/// `Poll::Pending`: parking on `Co::yield_` (a value is now sitting in the
+/// airlock) versus parking on some other future.
+///
+/// The airlock is checked and drained on every call, so a genuine external
+/// `Pending` is never mistaken for a yield: only a `Pending` poll *with* a
+/// value in the airlock is reported as [`EngineOutput::Yielded`].
+pub(crate) fn drive<Y, R, F: Future>(
+    future: Pin<&mut F>,
+    airlock: NonNull<UnsafeCell<Airlock<Y, R>>>,
+    cx: &mut Context<'_>,
+) -> Poll<EngineOutput<Y, F::Output>> {
+    match future.poll(cx) {
+        Poll::Ready(completion) => Poll::Ready(EngineOutput::Complete(completion)),
+        Poll::Pending => {
+            // Safety: see `Co::yield_`.
+            let slot = unsafe { &mut *airlock.as_ref().get() };
+            match slot.take_yielded() {
+                Some(value) => Poll::Ready(EngineOutput::Yielded(value)),
+                None => Poll::Pending,
+            }
+        }
+    }
+}
+
+/// A waker that does nothing, for use by synchronous drivers (`resume`,
+/// `resume_with`) that don't care about being woken back up.
+pub(crate) fn dummy_waker() -> std::task::Waker {
+    use std::task::{RawWaker, RawWakerVTable, Waker};
+
+    const VTABLE: RawWakerVTable =
+        RawWakerVTable::new(|_| RAW_WAKER, |_| {}, |_| {}, |_| {});
+    const RAW_WAKER: RawWaker = RawWaker::new(std::ptr::null(), &VTABLE);
+
+    unsafe { Waker::from_raw(RAW_WAKER) }
+}
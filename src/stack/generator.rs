@@ -0,0 +1,254 @@
+use std::{
+    cell::UnsafeCell, future::Future, marker::PhantomData, mem::ManuallyDrop, pin::Pin,
+    ptr::NonNull,
+};
+
+use crate::GeneratorState;
+
+use super::engine::{drive, dummy_waker, Airlock, Co, EngineOutput};
+
+/// Backing storage for a generator created with [`Gen::new`].
+///
+/// A `Shelf` holds the producer's future in place, so that the generator it
+/// backs doesn't need to allocate on the heap.
+///
+/// The future is wrapped in `ManuallyDrop` rather than stored as a plain
+/// `Option<F>`. A plain `Option<F>` gives dropck its own opinion about how
+/// long a `Gen` borrowing into this shelf may live relative to the shelf,
+/// which rejects the exact
+/// `let mut shelf = Shelf::new(); let mut gen = unsafe { Gen::new(&mut shelf, ...) };`
+/// pattern this module's docs rely on, because a type with drop glue for a
+/// generic parameter is required by dropck to strictly outlive anything
+/// borrowing out of it. `ManuallyDrop` opts out of that glue, at the cost of
+/// never running the producer's destructor: a `Shelf` going out of scope
+/// intentionally leaks whatever future it was backing, rather than dropping
+/// it.
+pub struct Shelf<Y, R, F> {
+    airlock: UnsafeCell<Airlock<Y, R>>,
+    future: ManuallyDrop<Option<F>>,
+}
+
+impl<Y, R, F> Shelf<Y, R, F> {
+    /// Creates a new shelf, ready to back a generator.
+    pub fn new() -> Self {
+        Shelf { airlock: UnsafeCell::new(Airlock::Empty), future: ManuallyDrop::new(None) }
+    }
+}
+
+impl<Y, R, F> Default for Shelf<Y, R, F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A generator backed by storage borrowed from a [`Shelf`].
+///
+/// See the [module-level docs](index.html) for examples.
+pub struct Gen<'s, Y, R, F: Future> {
+    pub(crate) airlock: NonNull<UnsafeCell<Airlock<Y, R>>>,
+    pub(crate) future: Pin<&'s mut F>,
+    done: bool,
+}
+
+impl<'s, Y, R, F: Future> Gen<'s, Y, R, F> {
+    /// Creates a new generator, using the given producer to build its
+    /// backing future.
+    ///
+    /// # Safety
+    ///
+    /// The `shelf` must not be reused to back a different generator while
+    /// this one is still alive.
+    pub unsafe fn new(
+        shelf: &'s mut Shelf<Y, R, F>,
+        producer: impl FnOnce(Co<'s, Y, R>) -> F,
+    ) -> Self {
+        let airlock = NonNull::new_unchecked(&mut shelf.airlock as *mut _);
+        let co = Co { airlock, phantom: PhantomData };
+        *shelf.future = Some(producer(co));
+        let future_ptr: *mut F = shelf.future.as_mut().unwrap_unchecked();
+        let future = Pin::new_unchecked(&mut *future_ptr);
+        Gen { airlock, future, done: false }
+    }
+
+    /// Returns `true` once the generator has produced its completion value.
+    ///
+    /// Once this is `true`, `resume`/`resume_with` will panic rather than
+    /// poll the (already finished) producer again.
+    pub(crate) fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Resumes execution of the generator.
+    ///
+    /// `R` must implement `Default` so that there's a well-defined value to
+    /// pass in, since (on the first resume) there's no producer code running
+    /// yet that could receive it.
+    pub fn resume(&mut self) -> GeneratorState<Y, F::Output>
+    where
+        R: Default,
+    {
+        self.resume_with(R::default())
+    }
+
+    /// Resumes execution of the generator, passing a value in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator has already completed &mdash; once `resume`
+    /// or `resume_with` has returned `Complete`, the producer is never
+    /// polled again, matching the `gen`-block RFC's "once done, stays done"
+    /// semantics.
+    ///
+    /// Also panics if the producer parks on a future other than
+    /// [`Co::yield_`]; such a producer must be driven as a `Stream` instead
+    /// (see the `futures03` feature).
+    pub fn resume_with(&mut self, resume_arg: R) -> GeneratorState<Y, F::Output> {
+        assert!(!self.done, "cannot resume a generator that has already completed");
+
+        // Safety: see `Co::yield_`.
+        unsafe {
+            *self.airlock.as_ref().get() = Airlock::Resumed(resume_arg);
+        }
+
+        let waker = dummy_waker();
+        let mut cx = std::task::Context::from_waker(&waker);
+        match drive(self.future.as_mut(), self.airlock, &mut cx) {
+            std::task::Poll::Ready(EngineOutput::Yielded(value)) => GeneratorState::Yielded(value),
+            std::task::Poll::Ready(EngineOutput::Complete(value)) => {
+                self.done = true;
+                GeneratorState::Complete(value)
+            }
+            std::task::Poll::Pending => panic!(
+                "generator parked on a future other than `Co::yield_`; drive it as a \
+                 `Stream` instead"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "futures03")]
+mod stream_impl {
+    use std::{
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use futures_core::Stream;
+
+    use super::{drive, Airlock, EngineOutput, Gen};
+    use std::future::Future;
+
+    impl<Y, R, F: Future> Stream for Gen<'_, Y, R, F>
+    where
+        R: Default,
+    {
+        type Item = Y;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            // Safety: `Gen` is `Unpin` (none of its fields borrow from `Gen`
+            // itself), so projecting out of the outer `Pin` is sound.
+            let this = unsafe { self.get_unchecked_mut() };
+            if this.is_done() {
+                return Poll::Ready(None);
+            }
+
+            // Just like `resume_with`, a value has to be sitting in the
+            // airlock before every poll: `YieldFut::poll` only resolves a
+            // parked `co.yield_(_).await` when it sees `Airlock::Resumed`,
+            // so without this a producer that yields more than once would
+            // stay parked there forever after its first value.
+            //
+            // Safety: see `Co::yield_`.
+            unsafe {
+                *this.airlock.as_ref().get() = Airlock::Resumed(R::default());
+            }
+
+            match drive(this.future.as_mut(), this.airlock, cx) {
+                Poll::Ready(EngineOutput::Yielded(value)) => Poll::Ready(Some(value)),
+                Poll::Ready(EngineOutput::Complete(_)) => {
+                    this.done = true;
+                    Poll::Ready(None)
+                }
+                Poll::Pending => Poll::Pending,
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::task::Context;
+
+        use super::*;
+        use crate::stack::{
+            engine::{dummy_waker, Co},
+            generator::Shelf,
+        };
+
+        /// A future that's `Pending` the first time it's polled, and `Ready`
+        /// every time after that, so tests can park a producer on something
+        /// other than `Co::yield_`.
+        struct PendingOnce(bool);
+
+        impl Future for PendingOnce {
+            type Output = ();
+
+            fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+                if self.0 {
+                    Poll::Ready(())
+                } else {
+                    self.0 = true;
+                    Poll::Pending
+                }
+            }
+        }
+
+        fn poll_next<Y, R: Default, F: Future>(gen: &mut Gen<'_, Y, R, F>) -> Poll<Option<Y>> {
+            let waker = dummy_waker();
+            let mut cx = Context::from_waker(&waker);
+            Pin::new(gen).poll_next(&mut cx)
+        }
+
+        #[test]
+        fn yields_every_value_across_multiple_polls() {
+            let mut shelf = Shelf::new();
+            let mut gen = unsafe {
+                Gen::new(&mut shelf, |mut co: Co<'_, i32>| async move {
+                    co.yield_(1).await;
+                    co.yield_(2).await;
+                })
+            };
+
+            assert_eq!(poll_next(&mut gen), Poll::Ready(Some(1)));
+            assert_eq!(poll_next(&mut gen), Poll::Ready(Some(2)));
+            assert_eq!(poll_next(&mut gen), Poll::Ready(None));
+        }
+
+        #[test]
+        fn propagates_pending_from_a_non_yield_future() {
+            let mut shelf = Shelf::new();
+            let mut gen = unsafe {
+                Gen::new(&mut shelf, |mut co: Co<'_, i32>| async move {
+                    co.yield_(1).await;
+                    PendingOnce(false).await;
+                    co.yield_(2).await;
+                })
+            };
+
+            assert_eq!(poll_next(&mut gen), Poll::Ready(Some(1)));
+            // Parked on `PendingOnce`, not `Co::yield_`: the airlock is empty,
+            // so this must not be mistaken for a yield.
+            assert_eq!(poll_next(&mut gen), Poll::Pending);
+            assert_eq!(poll_next(&mut gen), Poll::Ready(Some(2)));
+            assert_eq!(poll_next(&mut gen), Poll::Ready(None));
+        }
+
+        #[test]
+        fn stays_ready_none_after_completion() {
+            let mut shelf = Shelf::new();
+            let mut gen = unsafe { Gen::new(&mut shelf, |_: Co<'_, i32>| async move {}) };
+
+            assert_eq!(poll_next(&mut gen), Poll::Ready(None));
+            assert_eq!(poll_next(&mut gen), Poll::Ready(None));
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use std::{future::Future, iter::FusedIterator};
+
+use crate::GeneratorState;
+
+use super::generator::Gen;
+
+impl<Y, R, F> Iterator for Gen<'_, Y, R, F>
+where
+    F: Future,
+    R: Default,
+{
+    type Item = Y;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_done() {
+            return None;
+        }
+        match self.resume() {
+            GeneratorState::Yielded(y) => Some(y),
+            GeneratorState::Complete(_) => None,
+        }
+    }
+}
+
+// Once a `Gen` is done, `is_done` keeps `next` returning `None` without ever
+// resuming the producer again, regardless of how `resume`/`into_iter` calls
+// were interleaved beforehand.
+impl<Y, R, F> FusedIterator for Gen<'_, Y, R, F>
+where
+    F: Future,
+    R: Default,
+{
+}
+
+/// An iterator over a generator whose completion type is `Result<C, E>`.
+///
+/// Every `Yielded(v)` becomes `Some(Ok(v))`. The first `Complete(Err(e))`
+/// becomes `Some(Err(e))`; after that (or after a `Complete(Ok(_))`) the
+/// generator is never resumed again, and `next()` just returns `None`. This
+/// lets a generator body use `?` and have its caller write
+/// `for item in gen { let x = item?; ... }`.
+pub struct TryGen<'s, Y, R, C, E, F: Future<Output = Result<C, E>>> {
+    gen: Gen<'s, Y, R, F>,
+    done: bool,
+}
+
+impl<'s, Y, R, C, E, F> From<Gen<'s, Y, R, F>> for TryGen<'s, Y, R, C, E, F>
+where
+    F: Future<Output = Result<C, E>>,
+{
+    fn from(gen: Gen<'s, Y, R, F>) -> Self {
+        TryGen { gen, done: false }
+    }
+}
+
+impl<'s, Y, R, C, E, F> Iterator for TryGen<'s, Y, R, C, E, F>
+where
+    F: Future<Output = Result<C, E>>,
+    R: Default,
+{
+    type Item = Result<Y, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.gen.resume() {
+            GeneratorState::Yielded(y) => Some(Ok(y)),
+            GeneratorState::Complete(Ok(_)) => {
+                self.done = true;
+                None
+            }
+            GeneratorState::Complete(Err(e)) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl<'s, Y, R, C, E, F> FusedIterator for TryGen<'s, Y, R, C, E, F>
+where
+    F: Future<Output = Result<C, E>>,
+    R: Default,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stack::{engine::Co, generator::Shelf};
+
+    #[test]
+    fn fused_iterator_never_resumes_after_completion() {
+        let mut shelf = Shelf::new();
+        let mut gen = unsafe { Gen::new(&mut shelf, |_: Co<'_, i32>| async move {}) };
+
+        assert_eq!(gen.resume(), GeneratorState::Complete(()));
+        // `next` must see `is_done()` and return `None` without resuming the
+        // producer again; a second `resume_with` on a completed generator
+        // panics, so this would fail loudly if the guarantee broke.
+        assert_eq!(gen.next(), None);
+        assert_eq!(gen.next(), None);
+    }
+
+    #[test]
+    fn manual_resume_and_iterator_agree_on_fused_state() {
+        let mut shelf = Shelf::new();
+        let mut gen = unsafe {
+            Gen::new(&mut shelf, |mut co: Co<'_, i32>| async move {
+                co.yield_(1).await;
+            })
+        };
+
+        // Drive the first value by hand, then finish the generator off
+        // through the `Iterator` impl instead of another manual `resume()`.
+        assert_eq!(gen.resume(), GeneratorState::Yielded(1));
+        let rest: Vec<_> = gen.by_ref().collect();
+        assert_eq!(rest, Vec::<i32>::new());
+        assert_eq!(gen.next(), None);
+    }
+
+    #[test]
+    fn maps_yields_and_returns_the_first_error() {
+        let mut shelf = Shelf::new();
+        let gen = unsafe {
+            Gen::new(&mut shelf, |mut co: Co<'_, i32>| async move {
+                co.yield_(1).await;
+                co.yield_(2).await;
+                Err::<(), _>("ran out of numbers")
+            })
+        };
+        let mut iter = TryGen::from(gen);
+
+        assert_eq!(iter.next(), Some(Ok(1)));
+        assert_eq!(iter.next(), Some(Ok(2)));
+        assert_eq!(iter.next(), Some(Err("ran out of numbers")));
+        // Fused: once the error has been yielded, the producer is never
+        // resumed again.
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn fuses_to_none_after_an_ok_completion() {
+        let mut shelf = Shelf::new();
+        let gen = unsafe {
+            Gen::new(&mut shelf, |mut co: Co<'_, i32>| async move {
+                co.yield_(1).await;
+                Ok::<_, &'static str>(())
+            })
+        };
+        let mut iter = TryGen::from(gen);
+
+        assert_eq!(iter.next(), Some(Ok(1)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+    }
+}
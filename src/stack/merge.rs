@@ -0,0 +1,194 @@
+use std::future::Future;
+
+use crate::GeneratorState;
+
+use super::generator::Gen;
+
+/// Interleaves several stack generators into a single iterator, round-robin,
+/// completing once every generator has completed.
+///
+/// Every generator is resumed with `R::default()`; if any of them need
+/// resume arguments, drive them by hand instead.
+pub struct Merge<'s, Y, R, F: Future> {
+    gens: Vec<Gen<'s, Y, R, F>>,
+    next: usize,
+}
+
+impl<'s, Y, R, F: Future> Merge<'s, Y, R, F> {
+    /// Creates a combinator that interleaves the given generators.
+    pub fn new(gens: Vec<Gen<'s, Y, R, F>>) -> Self {
+        Merge { gens, next: 0 }
+    }
+}
+
+impl<'s, Y, R, F> Iterator for Merge<'s, Y, R, F>
+where
+    F: Future,
+    R: Default,
+{
+    type Item = Y;
+
+    /// Polls each not-yet-done generator in round-robin order, returning the
+    /// first value any of them yields. Returns `None` once every generator
+    /// has completed.
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = self.gens.len();
+        for _ in 0..len {
+            let i = self.next;
+            self.next = (self.next + 1) % len.max(1);
+            if self.gens[i].is_done() {
+                continue;
+            }
+            match self.gens[i].resume() {
+                GeneratorState::Yielded(value) => return Some(value),
+                GeneratorState::Complete(_) => continue,
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stack::{engine::Co, generator::Shelf};
+
+    async fn count_up_to(mut co: Co<'_, i32>, n: i32) {
+        for i in 1..=n {
+            co.yield_(i).await;
+        }
+    }
+
+    #[test]
+    fn interleaves_round_robin_until_every_generator_completes() {
+        let mut shelf_a = Shelf::new();
+        let mut shelf_b = Shelf::new();
+        let a = unsafe { Gen::new(&mut shelf_a, |co| count_up_to(co, 2)) };
+        let b = unsafe { Gen::new(&mut shelf_b, |co| count_up_to(co, 3)) };
+
+        let merged: Vec<_> = Merge::new(vec![a, b]).collect();
+        assert_eq!(merged, [1, 1, 2, 2, 3]);
+    }
+}
+
+#[cfg(feature = "futures03")]
+mod stream_impl {
+    use std::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use futures_core::Stream;
+
+    use super::Merge;
+
+    /// Readiness-driven mode: each poll advances whichever not-yet-done
+    /// generator becomes ready first, instead of blocking round-robin on
+    /// whichever generator happens to be polled first.
+    impl<Y, R, F: Future> Stream for Merge<'_, Y, R, F>
+    where
+        R: Default,
+    {
+        type Item = Y;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            // Safety: `Merge` is `Unpin` (its fields are a `Vec` of `Gen`s,
+            // which are themselves `Unpin`, and an index), so projecting out
+            // of the outer `Pin` is sound.
+            let this = unsafe { self.get_unchecked_mut() };
+            let len = this.gens.len();
+            if len == 0 {
+                return Poll::Ready(None);
+            }
+
+            for offset in 0..len {
+                let i = (this.next + offset) % len;
+                if this.gens[i].is_done() {
+                    continue;
+                }
+                match Pin::new(&mut this.gens[i]).poll_next(cx) {
+                    Poll::Ready(Some(value)) => {
+                        this.next = (i + 1) % len;
+                        return Poll::Ready(Some(value));
+                    }
+                    Poll::Ready(None) | Poll::Pending => continue,
+                }
+            }
+
+            // Check `is_done` again now that every not-yet-done child has
+            // just been polled: a child that completed during this call
+            // (rather than yielding) must count as done here too, or this
+            // would report `Pending` without having arranged any wake for a
+            // poll that has nothing left to become ready.
+            if this.gens.iter().all(|gen| gen.is_done()) {
+                Poll::Ready(None)
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::task::Context;
+
+        use super::*;
+        use crate::stack::{
+            engine::{dummy_waker, Co},
+            generator::{Gen, Shelf},
+        };
+
+        /// A future that's `Pending` the first time it's polled, and `Ready`
+        /// every time after that.
+        struct PendingOnce(bool);
+
+        impl Future for PendingOnce {
+            type Output = ();
+
+            fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+                if self.0 {
+                    Poll::Ready(())
+                } else {
+                    self.0 = true;
+                    Poll::Pending
+                }
+            }
+        }
+
+        // All generators merged together must share one producer type, so
+        // both children below are built from this single function, with
+        // `park_first` controlling whether a given one parks on a real
+        // future before yielding.
+        async fn maybe_park_then_yield(mut co: Co<'_, i32>, park_first: bool, value: i32) {
+            if park_first {
+                PendingOnce(false).await;
+            }
+            co.yield_(value).await;
+        }
+
+        #[test]
+        fn prefers_whichever_child_is_ready_over_the_round_robin_head() {
+            let mut shelf_a = Shelf::new();
+            let mut shelf_b = Shelf::new();
+            let a =
+                unsafe { Gen::new(&mut shelf_a, |co| maybe_park_then_yield(co, true, 1)) };
+            let b =
+                unsafe { Gen::new(&mut shelf_b, |co| maybe_park_then_yield(co, false, 2)) };
+            let mut merged = Merge::new(vec![a, b]);
+
+            let waker = dummy_waker();
+            let mut cx = Context::from_waker(&waker);
+
+            // `a` is the round-robin head but parked on a real pending
+            // future; `b` is immediately ready, so its value comes first.
+            assert_eq!(Pin::new(&mut merged).poll_next(&mut cx), Poll::Ready(Some(2)));
+            // Once `a`'s pending future resolves, it's driven straight
+            // through to its own yield in the same poll.
+            assert_eq!(Pin::new(&mut merged).poll_next(&mut cx), Poll::Ready(Some(1)));
+            // Both children complete on this same poll (neither has
+            // anything left to yield), so it's reported as done right away.
+            assert_eq!(Pin::new(&mut merged).poll_next(&mut cx), Poll::Ready(None));
+        }
+    }
+}
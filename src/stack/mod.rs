@@ -161,6 +161,12 @@ assert_eq!(numbers_then_string.resume(), GeneratorState::Complete("done!"));
 # }
 ```
 
+Once a generator has completed, it stays completed: calling `resume`/`resume_with`
+again panics rather than polling the producer a second time, and the `Iterator`
+impl implements [`FusedIterator`](std::iter::FusedIterator), so mixing `into_iter`
+with manual `resume` calls can never observe the producer running past its first
+`Complete`.
+
 ## Defining a reusable producer function
 
 ```rust
@@ -184,6 +190,8 @@ You can define an `async fn` directly, instead of relying on the `gen!` or `prod
 macros.
 
 ```rust
+# #[cfg(feature = "proc_macro")]
+# fn feature_gate() {
 use genawaiter::stack::{let_gen_using, Co};
 
 async fn producer(mut co: Co<'_, i32>) {
@@ -197,6 +205,7 @@ async fn producer(mut co: Co<'_, i32>) {
 let_gen_using!(odds_under_ten, producer);
 let result: Vec<_> = odds_under_ten.into_iter().collect();
 assert_eq!(result, [1, 3, 5, 7, 9]);
+# }
 ```
 
 ## Using the low-level API with an async closure (nightly Rust only)
@@ -216,6 +225,8 @@ assert_eq!(gen.resume(), GeneratorState::Complete(()));
 ## Using the low-level API with an async <del>closure</del> faux·sure (for stable Rust)
 
 ```
+# #[cfg(feature = "proc_macro")]
+# fn feature_gate() {
 # use genawaiter::{stack::let_gen_using, GeneratorState};
 #
 let_gen_using!(gen, |mut co| async move {
@@ -225,6 +236,7 @@ let_gen_using!(gen, |mut co| async move {
 assert_eq!(gen.resume(), GeneratorState::Yielded(10));
 assert_eq!(gen.resume(), GeneratorState::Yielded(20));
 assert_eq!(gen.resume(), GeneratorState::Complete(()));
+# }
 ```
 
 ## Using the low-level API with function arguments
@@ -232,6 +244,8 @@ assert_eq!(gen.resume(), GeneratorState::Complete(()));
 This is just ordinary Rust, nothing special.
 
 ```rust
+# #[cfg(feature = "proc_macro")]
+# fn feature_gate() {
 # use genawaiter::{stack::{let_gen_using, Co}, GeneratorState};
 #
 async fn multiples_of(num: i32, mut co: Co<'_, i32>) {
@@ -246,84 +260,196 @@ let_gen_using!(gen, |co| multiples_of(10, co));
 assert_eq!(gen.resume(), GeneratorState::Yielded(10));
 assert_eq!(gen.resume(), GeneratorState::Yielded(20));
 assert_eq!(gen.resume(), GeneratorState::Yielded(30));
+# }
+```
+
+## Awaiting other futures (the `futures03` feature)
+
+A producer isn't limited to awaiting [`Co::yield_`] &mdash; with the `futures03`
+feature enabled, `Gen` also implements [`futures_core::Stream`], so a producer
+that awaits arbitrary IO futures can be polled like any other stream. Each
+poll either drains a value that was placed in the airlock by `yield_`, or
+forwards the real waker into whatever other future the producer is waiting
+on.
+
+```ignore
+# use genawaiter::stack::{Co, Gen, Shelf};
+use futures::stream::StreamExt;
+
+async fn my_producer(mut co: Co<'_, u8>) {
+    some_io().await;
+    co.yield_(10).await;
+}
+
+let mut shelf = Shelf::new();
+let mut my_generator = unsafe { Gen::new(&mut shelf, my_producer) };
+while let Some(n) = my_generator.next().await {
+    println!("{}", n);
+}
+```
+
+## Fallible generators with `?`
+
+If a producer's completion type is `Result<C, E>`, wrap the `Gen` in
+[`TryGen`] to get an iterator of `Result<Y, E>`: each yielded value is
+`Ok`-wrapped, and the first error the body returns via `?` ends the
+iteration.
+
+```rust
+# #[cfg(feature = "proc_macro")]
+# fn feature_gate() {
+# use genawaiter::{stack::{let_gen_using, Co, TryGen}, GeneratorState};
+#
+async fn producer(mut co: Co<'_, i32>) -> Result<(), &'static str> {
+    co.yield_(1).await;
+    co.yield_(2).await;
+    Err("ran out of numbers")
+}
+
+let_gen_using!(gen, producer);
+let mut iter = TryGen::from(gen);
+assert_eq!(iter.next(), Some(Ok(1)));
+assert_eq!(iter.next(), Some(Ok(2)));
+assert_eq!(iter.next(), Some(Err("ran out of numbers")));
+assert_eq!(iter.next(), None);
+# }
+```
+
+## Merging several generators
+
+[`Merge`] drives a `Vec` of generators as a single iterator, returning each
+value as soon as its generator produces it (round-robin over the
+not-yet-completed ones), and finishing only once every generator has
+completed. With the `futures03` feature, `Merge` is also a `Stream` that
+polls whichever child becomes ready first, rather than blocking round-robin
+on one that's parked on a future.
+
+Every generator merged together has to share the same producer (so that
+they share the same underlying future type); pass the differing state in as
+an argument.
+
+```rust
+# #[cfg(feature = "proc_macro")]
+# fn feature_gate() {
+# use genawaiter::stack::{let_gen_using, Co, Merge};
+#
+async fn count_up_to(mut co: Co<'_, i32>, n: i32) {
+    for i in 1..=n {
+        co.yield_(i).await;
+    }
+}
+
+let_gen_using!(a, |co| count_up_to(co, 2));
+let_gen_using!(b, |co| count_up_to(co, 1));
+
+let merged: Vec<_> = Merge::new(vec![a, b]).collect();
+assert_eq!(merged, [1, 1, 2]);
+# }
 ```
 */
 
 pub use crate::stack::{
     engine::Co,
     generator::{Gen, Shelf},
+    iterator::TryGen,
+    merge::Merge,
 };
 
-/// Creates a generator.
-///
-/// The first argument is the name of the resulting variable.
-///
-/// ```ignore
-/// let_gen!(my_generator, { /* ... */ });
-/// // Think of this as the spiritual equivalent of:
-/// let mut my_generator = Gen::new(/* ... */);
-/// ```
-///
-/// The second argument is the body of the generator. It should contain one or
-/// more calls to the [`yield_!`] macro.
-///
-/// This macro is a shortcut for creating both a generator and its backing state
-/// (called a [`Shelf`](struct.Shelf.html)). If you (or your IDE) dislike
-/// macros, you can also do the bookkeeping by hand by using
-/// [`Gen::new`](struct.Gen.html#method.new), though note that this requires you
-/// to trade away safety.
-///
-/// # Examples
-///
-/// [_See the module-level docs for examples._](.)
-
-
-/// Creates a generator using a producer defined elsewhere.
-///
-/// The first argument is the name of the resulting variable.
-///
-/// ```ignore
-/// let_gen!(my_generator, { /* ... */ });
-/// // Think of this as the spiritual equivalent of:
-/// let mut my_generator = Gen::new(/* ... */);
-/// ```
-///
-/// The second line is the producer that will be used. It can be one of these
-/// two things:
-///
-/// 1.  The result of [`stack_producer!`] or [`stack_producer_fn!`]
-///
-///     [`stack_producer_fn!`]: attr.producer_fn.html
-///
-/// 2.  A function with this type:
-///
-///     ```ignore
-///     async fn producer(co: Co<'_, Yield, Resume>) -> Completion { /* ... */ }
-///     // which is equivalent to:
-///     fn producer(co: Co<'_, Yield, Resume>) -> impl Future<Output = Completion> { /* ... */ }
-///     ```
-///
-/// This macro is a shortcut for creating both a generator and its backing state
-/// (called a [`Shelf`](struct.Shelf.html)). If you (or your IDE) dislike
-/// macros, you can also do the bookkeeping by hand by using
-/// [`Gen::new`](struct.Gen.html#method.new), though note that this requires you
-/// to trade away safety.
-///
-/// # Examples
-///
-/// [_See the module-level docs for examples._](.)
+// Creates a generator.
+//
+// The first argument is the name of the resulting variable.
+//
+// ```ignore
+// let_gen!(my_generator, { /* ... */ });
+// // Think of this as the spiritual equivalent of:
+// let mut my_generator = Gen::new(/* ... */);
+// ```
+//
+// The second argument is the body of the generator. It should contain one or
+// more calls to the [`yield_!`] macro.
+//
+// This macro is a shortcut for creating both a generator and its backing state
+// (called a [`Shelf`](struct.Shelf.html)). If you (or your IDE) dislike
+// macros, you can also do the bookkeeping by hand by using
+// [`Gen::new`](struct.Gen.html#method.new), though note that this requires you
+// to trade away safety.
+//
+// # Examples
+//
+// [_See the module-level docs for examples._](.)
+//pub use genawaiter_macro::stack_let_gen as let_gen;
+
+// Creates a generator using a producer defined elsewhere.
+//
+// The first argument is the name of the resulting variable.
+//
+// ```ignore
+// let_gen!(my_generator, { /* ... */ });
+// // Think of this as the spiritual equivalent of:
+// let mut my_generator = Gen::new(/* ... */);
+// ```
+//
+// The second line is the producer that will be used. It can be one of these
+// two things:
+//
+// 1.  The result of [`stack_producer!`] or [`stack_producer_fn!`]
+//
+//     [`stack_producer_fn!`]: attr.producer_fn.html
+//
+// 2.  A function with this type:
+//
+//     ```ignore
+//     async fn producer(co: Co<'_, Yield, Resume>) -> Completion { /* ... */ }
+//     // which is equivalent to:
+//     fn producer(co: Co<'_, Yield, Resume>) -> impl Future<Output = Completion> { /* ... */ }
+//     ```
+//
+// This macro is a shortcut for creating both a generator and its backing state
+// (called a [`Shelf`](struct.Shelf.html)). If you (or your IDE) dislike
+// macros, you can also do the bookkeeping by hand by using
+// [`Gen::new`](struct.Gen.html#method.new), though note that this requires you
+// to trade away safety.
+//
+// # Examples
+//
+// [_See the module-level docs for examples._](.)
 //pub use genawaiter_macro::stack_let_gen_using as let_gen_using;
 
-/// Turns a function into a producer, which can then be used to create a
-/// generator.
-///
-/// The body of the function should contain one or more [`yield_!`] expressions.
-///
-/// # Examples
-///
-/// [_See the module-level docs for examples._](.)
+// Turns a function into a producer, which can then be used to create a
+// generator.
+//
+// The body of the function should contain one or more [`yield_!`] expressions.
+//
+// # Examples
+//
+// [_See the module-level docs for examples._](.)
+//pub use genawaiter_macro::stack_producer_fn as producer_fn;
+
+// Creates a fallible generator, i.e. one whose body may use `?`.
+//
+// This is exactly like [`let_gen!`], except the body's completion value is
+// wrapped in `Result`, and the resulting variable should be passed through
+// [`TryGen::from`] to get an iterator of `Result<Yield, Error>` rather than
+// a `Gen`.
+//
+// # Examples
+//
+// [_See the module-level docs for examples._](.)
+//pub use genawaiter_macro::stack_try_let_gen as try_let_gen;
+
+// Creates a fallible generator using a producer defined elsewhere.
+//
+// This is exactly like [`let_gen_using!`], except the producer's completion
+// type must be `Result<C, E>`; pass the resulting variable through
+// [`TryGen::from`] to get an iterator of `Result<Yield, Error>`.
+//
+// # Examples
+//
+// [_See the module-level docs for examples._](.)
+//pub use genawaiter_macro::stack_try_let_gen_using as try_let_gen_using;
 
 mod engine;
 mod generator;
 mod iterator;
+mod merge;
 